@@ -0,0 +1,73 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::receiver::Receiver;
+use crate::registry::Registry;
+
+/// Builds a [`Receiver`].
+#[derive(Default)]
+pub struct Builder {
+    #[cfg(feature = "metrics-tracing-context")]
+    with_tracing_context: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` with default values.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Enables merging the fields of the currently-active `tracing` span into the labels of every
+    /// metric recorded through a [`Sink`](crate::Sink) created from the resulting [`Receiver`].
+    ///
+    /// This has no effect unless a [`MetricsLayer`](crate::tracing_context::MetricsLayer) has
+    /// also been installed on the `tracing` subscriber in use.
+    #[cfg(feature = "metrics-tracing-context")]
+    pub fn with_tracing_context(mut self) -> Builder {
+        self.with_tracing_context = true;
+        self
+    }
+
+    /// Builds a new [`Receiver`] from this `Builder`.
+    pub fn build(self) -> Result<Receiver, BuilderError> {
+        Ok(Receiver::from_parts(
+            Arc::new(Registry::new()),
+            #[cfg(feature = "metrics-tracing-context")]
+            self.with_tracing_context,
+        ))
+    }
+}
+
+/// Errors that can occur while building a [`Receiver`].
+#[derive(Debug)]
+pub enum BuilderError {
+    /// The requested configuration could not be applied.
+    InvalidConfiguration(String),
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::InvalidConfiguration(reason) => {
+                write!(f, "invalid receiver configuration: {}", reason)
+            }
+        }
+    }
+}
+
+impl Error for BuilderError {}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+
+    #[test]
+    fn test_build_produces_a_working_receiver() {
+        let receiver = Builder::new().build().unwrap();
+        let sink = receiver.sink();
+        sink.increment_counter("widgets", 1);
+
+        assert_eq!(receiver.controller().snapshot().len(), 1);
+    }
+}
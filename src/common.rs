@@ -0,0 +1,70 @@
+//! Common types shared across the sink, registry, and control surfaces.
+use std::time::Instant;
+
+/// The scope under which a metric is registered.
+///
+/// Scopes let callers nest metrics hierarchically -- much like loggers -- without needing to
+/// manually build up the dotted name themselves.  See the [`Sink::scoped`](crate::Sink::scoped)
+/// documentation for more details on how scopes are used.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Scope {
+    /// The root scope, with no nesting.
+    Root,
+    /// A nested scope, with each element representing one level of nesting.
+    Nested(Vec<String>),
+}
+
+impl Scope {
+    /// Adds another level of nesting to this scope.
+    pub fn add_part<S: Into<String>>(&self, part: S) -> Scope {
+        match self {
+            Scope::Root => Scope::Nested(vec![part.into()]),
+            Scope::Nested(parts) => {
+                let mut parts = parts.clone();
+                parts.push(part.into());
+                Scope::Nested(parts)
+            }
+        }
+    }
+
+    /// Renders this scope as a dotted prefix, or `None` if this is the root scope.
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Scope::Root => None,
+            Scope::Nested(parts) => Some(parts.join(".")),
+        }
+    }
+}
+
+/// A value that can be measured for a given metric.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Measurement {
+    /// A monotonically increasing counter.
+    Counter(u64),
+    /// A point-in-time gauge value.
+    Gauge(i64),
+    /// A set of samples collected into a histogram.
+    Histogram(Vec<u64>),
+}
+
+/// A type whose difference from another value of the same type can be expressed as nanoseconds.
+///
+/// This allows [`Sink::record_timing`](crate::Sink::record_timing) to accept either raw `u64`
+/// nanosecond values, as returned by [`Sink::now`](crate::Sink::now), or [`Instant`] values
+/// directly.
+pub trait Delta {
+    /// Computes the delta between `self` and `other`, in nanoseconds.
+    fn delta(&self, other: Self) -> u64;
+}
+
+impl Delta for u64 {
+    fn delta(&self, other: u64) -> u64 {
+        self.saturating_sub(other)
+    }
+}
+
+impl Delta for Instant {
+    fn delta(&self, other: Instant) -> u64 {
+        self.saturating_duration_since(other).as_nanos() as u64
+    }
+}
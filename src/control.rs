@@ -0,0 +1,121 @@
+//! Pulling metrics back out of the system.
+use std::sync::Arc;
+
+use metrics_core::Key;
+
+use crate::common::Measurement;
+use crate::registry::Registry;
+
+/// A point-in-time snapshot of every metric known to a [`Registry`].
+pub type Snapshot = Vec<(Key, Measurement)>;
+
+/// Handle for taking snapshots of the metrics flowing through a [`Receiver`](crate::Receiver).
+///
+/// A `Controller` is cheap to clone, and can be freely shared between threads -- each clone reads
+/// from the same underlying registry.
+#[derive(Clone)]
+pub struct Controller {
+    registry: Arc<Registry>,
+}
+
+impl Controller {
+    /// Creates a new `Controller` backed by `registry`.
+    pub(crate) fn new(registry: Arc<Registry>) -> Controller {
+        Controller { registry }
+    }
+
+    /// Takes a snapshot of every counter, gauge, and histogram currently registered.
+    ///
+    /// Histogram samples are stored compressed internally, so taking a snapshot decompresses them
+    /// back into their raw values before handing them off as a
+    /// [`Measurement::Histogram`](crate::Measurement::Histogram).
+    ///
+    /// Every registered proxy (see [`Sink::proxy`](crate::Sink::proxy)) is also invoked, and its
+    /// measurements are added to the snapshot with the proxy's own name prepended.
+    pub fn snapshot(&self) -> Snapshot {
+        let mut results = Snapshot::new();
+
+        for (key, counter) in self.registry.counters() {
+            results.push((key, Measurement::Counter(counter.value())));
+        }
+
+        for (key, gauge) in self.registry.gauges() {
+            results.push((key, Measurement::Gauge(gauge.value())));
+        }
+
+        for (key, histogram) in self.registry.histograms() {
+            results.push((key, Measurement::Histogram(histogram.values())));
+        }
+
+        for (proxy_key, proxy) in self.registry.proxies() {
+            for (sub_key, measurement) in proxy() {
+                let name = format!("{}.{}", proxy_key.name(), sub_key.name());
+                results.push((Key::from_name(name), measurement));
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Receiver;
+    use metrics_core::Key;
+
+    use super::Measurement;
+
+    #[test]
+    fn test_snapshot_includes_counters_gauges_and_histograms() {
+        let receiver = Receiver::builder().build().unwrap();
+        let sink = receiver.sink();
+        sink.increment_counter("widgets", 5);
+        sink.update_gauge("red_balloons", 99);
+        sink.record_value("latency_ns", 42);
+
+        let snapshot = receiver.controller().snapshot();
+        assert!(snapshot
+            .iter()
+            .any(|(k, v)| k.name() == "widgets" && *v == Measurement::Counter(5)));
+        assert!(snapshot
+            .iter()
+            .any(|(k, v)| k.name() == "red_balloons" && *v == Measurement::Gauge(99)));
+        assert!(snapshot
+            .iter()
+            .any(|(k, v)| k.name() == "latency_ns" && *v == Measurement::Histogram(vec![42])));
+    }
+
+    #[test]
+    fn test_snapshot_includes_proxy_measurements_under_prefixed_name() {
+        let receiver = Receiver::builder().build().unwrap();
+        let sink = receiver.sink();
+        sink.proxy("load_stat", || {
+            vec![
+                (Key::from_name("avg_1min"), Measurement::Gauge(19)),
+                (Key::from_name("avg_5min"), Measurement::Gauge(12)),
+            ]
+        });
+
+        let snapshot = receiver.controller().snapshot();
+        assert!(snapshot
+            .iter()
+            .any(|(k, v)| k.name() == "load_stat.avg_1min" && *v == Measurement::Gauge(19)));
+        assert!(snapshot
+            .iter()
+            .any(|(k, v)| k.name() == "load_stat.avg_5min" && *v == Measurement::Gauge(12)));
+    }
+
+    #[test]
+    fn test_proxy_name_is_scoped_like_any_other_metric() {
+        let receiver = Receiver::builder().build().unwrap();
+        let sink = receiver.sink().scoped("system");
+        sink.proxy("load_stat", || {
+            vec![(Key::from_name("avg_1min"), Measurement::Gauge(19))]
+        });
+
+        let snapshot = receiver.controller().snapshot();
+        assert!(snapshot
+            .iter()
+            .any(|(k, _)| k.name() == "system.load_stat.avg_1min"));
+    }
+}
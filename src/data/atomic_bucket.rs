@@ -0,0 +1,312 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+
+use crate::stream::StreamingIntegers;
+
+/// Number of slots held by a single block (1 KiB of values, or sixteen 64-byte cache lines) --
+/// big enough to amortize the cost of allocating a new block, small enough that a burst of
+/// writers isn't stuck waiting on a single allocation.
+const BLOCK_SIZE: usize = 128;
+
+/// A single fixed-size block of slots in the bucket's linked list.
+struct Block {
+    /// Values written into this block.  A slot is only valid for reading once its corresponding
+    /// bit in `filled` is set.
+    values: [AtomicU64; BLOCK_SIZE],
+    /// Tracks which slots in `values` have been written.
+    filled: [AtomicUsize; BLOCK_SIZE],
+    /// The next index to hand out to a writer via `fetch_add`.  Once this exceeds `BLOCK_SIZE`,
+    /// the block is full and a new one must be linked in ahead of it.
+    cursor: AtomicUsize,
+    /// The next (older) block in the list, or a null shared pointer for the last block.
+    next: Atomic<Block>,
+}
+
+impl Block {
+    fn new() -> Block {
+        Block {
+            values: [0; BLOCK_SIZE].map(AtomicU64::new),
+            filled: [0; BLOCK_SIZE].map(AtomicUsize::new),
+            cursor: AtomicUsize::new(0),
+            next: Atomic::null(),
+        }
+    }
+
+    /// Returns the values this block holds that are safe to read right now.
+    fn filled_values(&self) -> Vec<u64> {
+        let filled = self.cursor.load(Ordering::Acquire).min(BLOCK_SIZE);
+        (0..filled)
+            .filter(|&i| self.filled[i].load(Ordering::Acquire) == 1)
+            .map(|i| self.values[i].load(Ordering::Acquire))
+            .collect()
+    }
+}
+
+/// A lock-free, append-only bucket of `u64` samples.
+///
+/// `AtomicBucket` is a singly-linked list of fixed-size [`Block`]s.  Writers never block one
+/// another: each one atomically claims a slot in the current head block via `fetch_add`, writes
+/// its value into that slot, and marks it filled.  When a block fills up, every writer that claims
+/// an out-of-bounds slot allocates a fresh block and tries to CAS-link it in as the new head,
+/// leaving the full block reachable from it via `next`; the first CAS wins, and the other writers
+/// drop their block and retry against the new head.
+///
+/// Blocks behind the head are folded into a [`StreamingIntegers`] stream by
+/// [`AtomicBucket::compact`], which every snapshot (`values`) runs first.  Between snapshots, full
+/// blocks accumulate uncompressed.  The compressed stream keeps every sample ever pushed, so memory
+/// still grows with the total number of samples, at the compressed size per sample rather than a
+/// full `u64`.
+///
+/// Snapshotting (`values`) walks the live blocks under an [`epoch`](crossbeam_epoch) guard, which
+/// keeps every block we might still be reading from alive even if writers have since moved past
+/// them, and never needs to pause writers to do so.
+pub struct AtomicBucket {
+    head: Atomic<Block>,
+    compacted: Mutex<StreamingIntegers>,
+}
+
+impl Default for AtomicBucket {
+    fn default() -> AtomicBucket {
+        AtomicBucket::new()
+    }
+}
+
+impl AtomicBucket {
+    /// Creates a new, empty `AtomicBucket`.
+    pub fn new() -> AtomicBucket {
+        AtomicBucket {
+            head: Atomic::new(Block::new()),
+            compacted: Mutex::new(StreamingIntegers::new()),
+        }
+    }
+
+    /// Pushes a single value into the bucket.
+    pub fn push(&self, value: u64) {
+        let guard = &epoch::pin();
+        loop {
+            let head_shared = self.head.load(Ordering::Acquire, guard);
+            // SAFETY: the head pointer is never unlinked while readers/writers can still observe
+            // it, only ever replaced; the epoch guard keeps it alive for the duration of this call.
+            let head = unsafe { head_shared.deref() };
+
+            let index = head.cursor.fetch_add(1, Ordering::AcqRel);
+            if index < BLOCK_SIZE {
+                head.values[index].store(value, Ordering::Release);
+                head.filled[index].store(1, Ordering::Release);
+                return;
+            }
+
+            // The current head is full (or another writer is in the process of filling it); try
+            // to install a new head block that points back at the full one.
+            let new_block = Owned::new(Block::new());
+            new_block.next.store(head_shared, Ordering::Relaxed);
+
+            match self.head.compare_exchange(
+                head_shared,
+                new_block,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                guard,
+            ) {
+                Ok(_) => continue,
+                Err(_) => continue,
+            }
+        }
+    }
+
+    /// Folds every block behind the current head into this bucket's compressed tail.
+    ///
+    /// Right after this runs, the only uncompressed block is the head (at most `BLOCK_SIZE` raw
+    /// samples), unless writers have linked in newer heads meanwhile.  It's safe to call
+    /// concurrently with `push`, and a no-op if there's nothing behind the head to fold in yet.
+    /// Every slot of a block behind the head has already been claimed, so before folding a block
+    /// in, `compact` waits for each claimed slot to be written; no value is lost when the block is
+    /// freed.
+    pub fn compact(&self) {
+        let guard = &epoch::pin();
+        let head_shared = self.head.load(Ordering::Acquire, guard);
+        if head_shared.is_null() {
+            return;
+        }
+        // SAFETY: see `push`.
+        let head = unsafe { head_shared.deref() };
+        let tail = head.next.swap(Shared::null(), Ordering::AcqRel, guard);
+        if tail.is_null() {
+            return;
+        }
+
+        let blocks = collect_blocks(tail, guard);
+
+        let mut compacted = self.compacted.lock().unwrap();
+        for block in blocks.iter().rev() {
+            for filled in block.filled.iter() {
+                while filled.load(Ordering::Acquire) != 1 {
+                    std::hint::spin_loop();
+                }
+            }
+            compacted.compress(&block.filled_values());
+        }
+        drop(compacted);
+
+        let mut current = tail;
+        while !current.is_null() {
+            // SAFETY: `current` was unlinked above, so no new reader can reach it; the epoch
+            // guard defers the actual free until every guard pinned before the unlink is gone, so
+            // any writer still mid-`push` against this block stays sound.
+            let block = unsafe { current.deref() };
+            let next = block.next.load(Ordering::Acquire, guard);
+            unsafe {
+                guard.defer_destroy(current);
+            }
+            current = next;
+        }
+    }
+
+    /// Returns every value currently held by the bucket, oldest-written value first.
+    ///
+    /// Values are read without ever blocking a concurrent writer.  A value whose slot has been
+    /// claimed but not yet written at the moment of the read may or may not be present in the
+    /// result -- the same approximate-snapshot tradeoff any lock-free structure like this makes.
+    pub fn values(&self) -> Vec<u64> {
+        self.compact();
+
+        let guard = &epoch::pin();
+        let head_shared = self.head.load(Ordering::Acquire, guard);
+        let blocks = collect_blocks(head_shared, guard);
+
+        let mut out = self.compacted.lock().unwrap().decompress().collect::<Vec<_>>();
+        for block in blocks.iter().rev() {
+            out.extend(block.filled_values());
+        }
+        out
+    }
+}
+
+/// Walks the linked list starting at `start`, returning every block reachable from it, newest
+/// first.
+fn collect_blocks<'g>(start: Shared<'g, Block>, guard: &'g Guard) -> Vec<&'g Block> {
+    let mut blocks = Vec::new();
+    let mut current = start;
+    while !current.is_null() {
+        // SAFETY: the epoch guard pins the current epoch for the lifetime of `guard`, so no
+        // block reachable from `start` at the start of this walk can be reclaimed underneath us.
+        let block = unsafe { current.deref() };
+        blocks.push(block);
+        current = block.next.load(Ordering::Acquire, guard);
+    }
+    blocks
+}
+
+impl Drop for AtomicBucket {
+    fn drop(&mut self) {
+        // We hold `&mut self`, so no concurrent `push`/`compact`/`values` call can be in flight;
+        // an unprotected guard is sound here, and every remaining block can be freed directly
+        // rather than deferred.
+        let guard = unsafe { epoch::unprotected() };
+        let mut current = self.head.load(Ordering::Relaxed, guard);
+        while !current.is_null() {
+            // SAFETY: `self` is being dropped and nothing else holds a reference into this list.
+            let owned = unsafe { current.into_owned() };
+            let next = owned.next.load(Ordering::Relaxed, guard);
+            drop(owned);
+            current = next;
+        }
+    }
+}
+
+// SAFETY: all access to block contents goes through atomics; the linked list is extended by
+// `push` and unlinked only by `compact`, which frees unlinked blocks through the epoch guard.
+unsafe impl Send for AtomicBucket {}
+unsafe impl Sync for AtomicBucket {}
+
+impl fmt::Debug for AtomicBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicBucket").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AtomicBucket, BLOCK_SIZE};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_empty() {
+        let bucket = AtomicBucket::new();
+        assert_eq!(bucket.values(), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_single_block_preserves_values() {
+        let bucket = AtomicBucket::new();
+        for i in 0..10 {
+            bucket.push(i);
+        }
+        assert_eq!(bucket.values(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_spans_multiple_blocks() {
+        let bucket = AtomicBucket::new();
+        let total = BLOCK_SIZE * 3 + 7;
+        for i in 0..total as u64 {
+            bucket.push(i);
+        }
+        assert_eq!(bucket.values(), (0..total as u64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_compact_preserves_values_and_order() {
+        let bucket = AtomicBucket::new();
+        let total = BLOCK_SIZE * 2 + 3;
+        for i in 0..total as u64 {
+            bucket.push(i);
+        }
+        bucket.compact();
+        assert_eq!(bucket.values(), (0..total as u64).collect::<Vec<_>>());
+
+        // Compacting again, with nothing new behind the head, is a no-op.
+        bucket.compact();
+        assert_eq!(bucket.values(), (0..total as u64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_concurrent_push_and_read() {
+        let bucket = Arc::new(AtomicBucket::new());
+        let writers = 8;
+        let per_writer = BLOCK_SIZE * 4;
+
+        let handles: Vec<_> = (0..writers)
+            .map(|_| {
+                let bucket = Arc::clone(&bucket);
+                thread::spawn(move || {
+                    for i in 0..per_writer as u64 {
+                        bucket.push(i);
+                    }
+                })
+            })
+            .collect();
+
+        // Read concurrently with the writers above; this should never panic or deadlock, and
+        // every value observed must be one we actually pushed.
+        let reader_bucket = Arc::clone(&bucket);
+        let reader = thread::spawn(move || {
+            for _ in 0..20 {
+                for value in reader_bucket.values() {
+                    assert!(value < per_writer as u64);
+                }
+            }
+        });
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        reader.join().unwrap();
+
+        assert_eq!(bucket.values().len(), writers * per_writer);
+    }
+}
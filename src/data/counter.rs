@@ -0,0 +1,35 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A monotonically increasing counter.
+///
+/// Counters are cheap to update, and can be cloned freely -- all clones refer to the same
+/// underlying value.
+#[derive(Clone, Debug, Default)]
+pub struct Counter {
+    value: Arc<AtomicU64>,
+}
+
+impl Counter {
+    /// Creates a new `Counter` starting at zero.
+    pub fn new() -> Counter {
+        Counter {
+            value: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Increments this counter by one.
+    pub fn increment(&self) {
+        self.record(1);
+    }
+
+    /// Increments this counter by `value`.
+    pub fn record(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Release);
+    }
+
+    /// Returns the current value of this counter.
+    pub fn value(&self) -> u64 {
+        self.value.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+/// A point-in-time value that can go up or down.
+///
+/// Gauges hold on to the last value they were updated to, and can be cloned freely -- all clones
+/// refer to the same underlying value.
+#[derive(Clone, Debug, Default)]
+pub struct Gauge {
+    value: Arc<AtomicI64>,
+}
+
+impl Gauge {
+    /// Creates a new `Gauge` starting at zero.
+    pub fn new() -> Gauge {
+        Gauge {
+            value: Arc::new(AtomicI64::new(0)),
+        }
+    }
+
+    /// Sets this gauge to `value`.
+    pub fn record(&self, value: i64) {
+        self.value.store(value, Ordering::Release);
+    }
+
+    /// Returns the current value of this gauge.
+    pub fn value(&self) -> i64 {
+        self.value.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::common::Delta;
+use crate::data::atomic_bucket::AtomicBucket;
+
+/// A collection of sampled values.
+///
+/// Samples are stored in a lock-free [`AtomicBucket`], so concurrent `record_value`/
+/// `record_timing` calls from many [`Sink`](crate::Sink)s never block each other, and histogram
+/// ingestion scales with the number of cores recording to it rather than contending on a single
+/// lock.  On each snapshot the bucket folds every block of samples but its most recent one into a
+/// compressed [`stream`](crate::stream), so older samples cost their compressed size rather than a
+/// full `u64` each; samples are kept for the life of the histogram, so memory still grows with
+/// their number.  Handles can be cloned freely; all clones record into the same underlying storage.
+#[derive(Clone, Debug, Default)]
+pub struct Histogram {
+    inner: Arc<AtomicBucket>,
+}
+
+impl Histogram {
+    /// Creates a new, empty `Histogram`.
+    pub fn new() -> Histogram {
+        Histogram {
+            inner: Arc::new(AtomicBucket::new()),
+        }
+    }
+
+    /// Records a single value into this histogram.
+    pub fn record_value(&self, value: u64) {
+        self.inner.push(value);
+    }
+
+    /// Records the delta between `start` and `end`, in nanoseconds, into this histogram.
+    pub fn record_timing<D: Delta>(&self, start: D, end: D) {
+        self.record_value(end.delta(start));
+    }
+
+    /// Returns all values currently held by this histogram.
+    pub fn values(&self) -> Vec<u64> {
+        self.inner.values()
+    }
+}
@@ -0,0 +1,11 @@
+//! Metric handle types.
+//!
+//! These are the concrete types returned by [`Sink::counter`](crate::Sink::counter),
+//! [`Sink::gauge`](crate::Sink::gauge), and [`Sink::histogram`](crate::Sink::histogram), and allow
+//! updating a specific metric directly without needing to look it up by name on every call.
+mod atomic_bucket;
+mod counter;
+mod gauge;
+mod histogram;
+
+pub use self::{counter::Counter, gauge::Gauge, histogram::Histogram};
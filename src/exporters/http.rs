@@ -0,0 +1,55 @@
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener};
+
+use metrics_core::{Builder, Drain, Observer};
+
+use crate::control::Controller;
+use crate::exporters::render_snapshot;
+
+/// Exports metrics by serving the latest snapshot, rendered via a [`Builder`], over a plain HTTP
+/// endpoint -- a scrape target for Prometheus and similar pull-based collectors.
+pub struct HttpExporter<B: Builder> {
+    controller: Controller,
+    builder: B,
+    address: SocketAddr,
+}
+
+impl<B: Builder> HttpExporter<B>
+where
+    B::Output: Observer + Drain<String>,
+{
+    /// Creates a new `HttpExporter`, serving snapshots from `controller` rendered with `builder`
+    /// on `address`.
+    pub fn new(controller: Controller, builder: B, address: SocketAddr) -> Self {
+        HttpExporter {
+            controller,
+            builder,
+            address,
+        }
+    }
+
+    /// Runs this exporter on the current thread, blocking forever, answering every incoming
+    /// connection with a freshly rendered snapshot.
+    pub fn run(&mut self) {
+        let listener = TcpListener::bind(self.address).expect("failed to bind HTTP listener");
+
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+
+            let body = self.render();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    }
+
+    fn render(&mut self) -> String {
+        render_snapshot(&self.controller, &self.builder)
+    }
+}
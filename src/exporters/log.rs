@@ -0,0 +1,47 @@
+use std::thread;
+use std::time::Duration;
+
+use log::Level;
+use metrics_core::{Builder, Drain, Observer};
+
+use crate::control::Controller;
+use crate::exporters::render_snapshot;
+
+/// Exports metrics by rendering them via a [`Builder`] and writing the result out through the
+/// `log` facade on a fixed interval.
+pub struct LogExporter<B: Builder> {
+    controller: Controller,
+    builder: B,
+    level: Level,
+    interval: Duration,
+}
+
+impl<B: Builder> LogExporter<B>
+where
+    B::Output: Observer + Drain<String>,
+{
+    /// Creates a new `LogExporter`, rendering snapshots from `controller` with `builder` and
+    /// logging them at `level` every `interval`.
+    pub fn new(controller: Controller, builder: B, level: Level, interval: Duration) -> Self {
+        LogExporter {
+            controller,
+            builder,
+            level,
+            interval,
+        }
+    }
+
+    /// Runs this exporter on the current thread, blocking forever.
+    pub fn run(&mut self) {
+        loop {
+            thread::sleep(self.interval);
+            self.turn();
+        }
+    }
+
+    /// Takes a single snapshot, renders it, and logs it immediately.
+    pub fn turn(&mut self) {
+        let rendered = render_snapshot(&self.controller, &self.builder);
+        log::log!(self.level, "{}", rendered);
+    }
+}
@@ -0,0 +1,56 @@
+//! Pre-baked ways of getting rendered snapshots out of the system, whether that's to a log, an
+//! HTTP scrape endpoint, or a raw TCP stream.
+#[cfg(feature = "metrics-exporter-http")]
+mod http;
+#[cfg(feature = "metrics-exporter-log")]
+mod log;
+#[cfg(feature = "metrics-exporter-tcp")]
+mod tcp;
+
+#[cfg(feature = "metrics-exporter-http")]
+pub use self::http::HttpExporter;
+#[cfg(feature = "metrics-exporter-log")]
+pub use self::log::LogExporter;
+#[cfg(feature = "metrics-exporter-tcp")]
+pub use self::tcp::TcpExporter;
+
+#[cfg(any(
+    feature = "metrics-exporter-http",
+    feature = "metrics-exporter-log",
+    feature = "metrics-exporter-tcp"
+))]
+use metrics_core::{Builder, Drain, Observer};
+
+#[cfg(any(
+    feature = "metrics-exporter-http",
+    feature = "metrics-exporter-log",
+    feature = "metrics-exporter-tcp"
+))]
+use crate::control::Controller;
+
+/// Takes a snapshot from `controller`, dispatches every measurement in it to a freshly built
+/// `B::Output` observer, and drains the result -- the common core of every exporter's render
+/// step, regardless of how the rendered string is then delivered.
+#[cfg(any(
+    feature = "metrics-exporter-http",
+    feature = "metrics-exporter-log",
+    feature = "metrics-exporter-tcp"
+))]
+fn render_snapshot<B>(controller: &Controller, builder: &B) -> String
+where
+    B: Builder,
+    B::Output: Observer + Drain<String>,
+{
+    let snapshot = controller.snapshot();
+    let mut observer = builder.build();
+
+    for (key, measurement) in snapshot {
+        match measurement {
+            crate::Measurement::Counter(value) => observer.observe_counter(key, value),
+            crate::Measurement::Gauge(value) => observer.observe_gauge(key, value),
+            crate::Measurement::Histogram(values) => observer.observe_histogram(key, &values),
+        }
+    }
+
+    observer.drain()
+}
@@ -0,0 +1,122 @@
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use metrics_core::{Builder, Drain, Observer};
+
+use crate::control::Controller;
+use crate::exporters::render_snapshot;
+
+/// How long a single write to a client may take before it's considered too slow to keep up, and
+/// is dropped rather than allowed to stall the snapshot loop.
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Streams metric snapshots to every connected client as they're taken, rather than requiring
+/// clients to pull via a scrape endpoint.
+///
+/// Connecting to a `TcpExporter` (e.g. with `nc host port`) gives a live, `tail -f`-style view of
+/// a running service's counters, gauges, and histograms, rendered with the same
+/// [`Builder`]/[`Observer`] pair used by the other exporters.  Slow or disconnected clients are
+/// dropped as soon as a write to them fails or times out, so one stuck reader can never stall the
+/// snapshot loop for everyone else.
+pub struct TcpExporter<B: Builder> {
+    controller: Controller,
+    builder: B,
+    listener: TcpListener,
+    interval: Duration,
+    clients: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+impl<B: Builder> TcpExporter<B>
+where
+    B::Output: Observer + Drain<String>,
+{
+    /// Creates a new `TcpExporter`, binding a listening socket at `address` and rendering a
+    /// snapshot from `controller` with `builder` to every connected client every `interval`.
+    pub fn new(
+        controller: Controller,
+        builder: B,
+        address: SocketAddr,
+        interval: Duration,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+
+        Ok(TcpExporter {
+            controller,
+            builder,
+            listener,
+            interval,
+            clients: Arc::new(Mutex::new(Vec::new())),
+        })
+    }
+
+    /// Runs this exporter on the current thread, blocking forever.
+    ///
+    /// A background thread accepts incoming connections and hands them off to the snapshot loop;
+    /// the loop itself never blocks on `accept`.
+    pub fn run(&mut self) {
+        self.spawn_acceptor();
+
+        loop {
+            thread::sleep(self.interval);
+            self.turn();
+        }
+    }
+
+    /// Spawns the background thread that accepts new client connections.
+    fn spawn_acceptor(&self) {
+        let listener = self
+            .listener
+            .try_clone()
+            .expect("failed to clone TCP listener");
+        let clients = Arc::clone(&self.clients);
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = stream.set_write_timeout(Some(CLIENT_WRITE_TIMEOUT));
+                clients.lock().unwrap().push(stream);
+            }
+        });
+    }
+
+    /// Takes a single snapshot, renders it, and pushes it out to every connected client, dropping
+    /// any client whose write fails or times out.
+    pub fn turn(&mut self) {
+        let rendered = self.render();
+        let bytes = rendered.as_bytes();
+
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain_mut(|client| client.write_all(bytes).is_ok());
+    }
+
+    fn render(&mut self) -> String {
+        render_snapshot(&self.controller, &self.builder)
+    }
+
+    /// Converts this exporter into a future that can be spawned onto any Tokio-compatible
+    /// runtime, running the same accept/push loop as [`TcpExporter::run`] without blocking the
+    /// calling thread.
+    ///
+    /// Each turn's snapshot render and per-client writes run on Tokio's blocking thread pool via
+    /// [`spawn_blocking`](tokio::task::spawn_blocking) rather than inline in this `async fn`, so a
+    /// client stalled up to `CLIENT_WRITE_TIMEOUT` never holds up the executor thread this future
+    /// is polled on.
+    pub async fn into_future(mut self)
+    where
+        B: Send + 'static,
+    {
+        self.spawn_acceptor();
+
+        loop {
+            tokio::time::sleep(self.interval).await;
+            self = tokio::task::spawn_blocking(move || {
+                self.turn();
+                self
+            })
+            .await
+            .expect("tcp exporter snapshot task panicked");
+        }
+    }
+}
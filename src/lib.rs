@@ -111,7 +111,7 @@
 //! // nesting N levels deep.
 //! //
 //! // This metric name will end up being "super.secret.ultra.special.widgets".
-//! let mut scoped_sink_three = scoped_sink.scoped(&["super", "secret", "ultra", "special"]);
+//! let mut scoped_sink_three = scoped_sink.scoped(&["super", "secret", "ultra", "special"][..]);
 //! scoped_sink_two.increment_counter("widgets", 42);
 //! ```
 //!
@@ -251,7 +251,10 @@
 //!
 //! Let's take an example of writing out our metrics in a yaml-like format, writing them via
 //! `log!`:
-//! ```rust
+//!
+//! This example requires the `metrics-observer-yaml` and `metrics-exporter-log` features, so it
+//! isn't compiled as part of the doc tests.
+//! ```rust,ignore
 //! # extern crate ckb_metrics_runtime as metrics_runtime;
 //! use metrics_runtime::{
 //!     Receiver, observers::YamlBuilder, exporters::LogExporter,
@@ -309,25 +312,36 @@
 //! counter!("items_processed", 42);
 //! ```
 //!
+//! # Tracing context
+//!
+//! When built with the `metrics-tracing-context` feature, a [`Receiver`] can be configured with
+//! [`Builder::with_tracing_context`] to automatically merge the fields of the currently-active
+//! `tracing` span into the labels of every metric a [`Sink`] records, after that `Sink`'s default
+//! labels and before any labels passed in for a specific call. This requires installing
+//! [`tracing_context::MetricsLayer`] on your `tracing` subscriber; see that module for details.
+//!
 //! [metrics_core]: https://docs.rs/metrics-core
 //! [`Observer`]: https://docs.rs/metrics-core/0.3.1/metrics_core/trait.Observer.html
 #![deny(missing_docs)]
 #![warn(unused_extern_crates)]
 mod builder;
 mod common;
-mod config;
 mod control;
 pub mod data;
-mod helper;
-mod macros;
 mod receiver;
 pub mod recorder;
 mod registry;
 mod sink;
 
-#[cfg(any(feature = "metrics-exporter-log", feature = "metrics-exporter-http"))]
+#[cfg(any(
+    feature = "metrics-exporter-log",
+    feature = "metrics-exporter-http",
+    feature = "metrics-exporter-tcp"
+))]
 pub mod exporters;
-/// I want to link to [`Nonexistent`] but it doesn't exist!
+/// Compressed storage for streams of integer samples, used internally by
+/// [`Histogram`](crate::data::Histogram) to keep memory usage down for high-cardinality timing
+/// histograms.
 pub mod stream;
 
 #[cfg(any(
@@ -337,6 +351,9 @@ pub mod stream;
 ))]
 pub mod observers;
 
+#[cfg(feature = "metrics-tracing-context")]
+pub mod tracing_context;
+
 pub use self::{
     builder::{Builder, BuilderError},
     common::{Delta, Measurement, Scope},
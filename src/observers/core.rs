@@ -0,0 +1,211 @@
+//! Shared quantile/summarization logic used by every observer.
+
+/// A quantile to compute over a histogram's samples, such as the 50th or 99.9th percentile.
+///
+/// Alongside the raw fraction, a `Quantile` carries two human-readable labels derived from it:
+/// [`Quantile::label`], a fraction-style label (e.g. `0.999 -> "999"`, `1.0 -> "1"`), and
+/// [`Quantile::percentile`], a percentile-style label (e.g.
+/// `0.5 -> "50"`, for building a field name like `db.query_ns.p50`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantile(f64, String, String);
+
+impl Quantile {
+    /// Creates a new `Quantile` from a fraction in `[0.0, 1.0]`.
+    ///
+    /// Fractions outside that range are clamped rather than rejected, since `summarize`'s rank
+    /// calculation relies on every `Quantile` it's given holding a value in range.
+    pub fn new(quantile: f64) -> Quantile {
+        let quantile = quantile.clamp(0.0, 1.0);
+        let label = format_label(quantile);
+        let percentile = format_percentile_label(quantile);
+        Quantile(quantile, label, percentile)
+    }
+
+    /// Returns the raw fraction this quantile represents.
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns the fraction-style label for this quantile, e.g. `"999"` for the 99.9th
+    /// percentile or `"1"` for the 100th.
+    pub fn label(&self) -> &str {
+        &self.1
+    }
+
+    /// Returns the percentile-style label for this quantile, e.g. `"50"` for the 50th percentile
+    /// or `"999"` for the 99.9th, suitable for building a field name like `db.query_ns.p50`.
+    pub fn percentile(&self) -> &str {
+        &self.2
+    }
+}
+
+impl From<f64> for Quantile {
+    fn from(quantile: f64) -> Quantile {
+        Quantile::new(quantile)
+    }
+}
+
+/// Derives a label from a quantile fraction by dropping the leading `"0."`, trailing zeroes, and
+/// any trailing decimal point left behind (e.g. for `1.0`), e.g. `0.5 -> "5"`, `0.99 -> "99"`,
+/// `0.999 -> "999"`, `1.0 -> "1"`.
+fn format_label(quantile: f64) -> String {
+    let s = format!("{:.6}", quantile);
+    let trimmed = s
+        .trim_start_matches("0.")
+        .trim_end_matches('0')
+        .trim_end_matches('.');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Derives a percentile-style label from a quantile fraction, e.g. `0.5 -> "50"`,
+/// `0.9 -> "90"`, `0.99 -> "99"`, `0.999 -> "999"`.
+///
+/// Unlike `format_label`, this scales the fraction up to a percentage first, so the digits that
+/// place it on a 0-100 scale (e.g. the `5` in `50`) are never mistaken for insignificant trailing
+/// zeroes -- only zeroes after the decimal point are trimmed, and the point itself is then
+/// dropped to fold any remaining fractional digits into the label (e.g. `99.9 -> "999"`).
+fn format_percentile_label(quantile: f64) -> String {
+    let percentage = quantile * 100.0;
+    let s = format!("{:.6}", percentage);
+    let trimmed = s.trim_end_matches('0').trim_end_matches('.');
+    trimmed.replace('.', "")
+}
+
+/// The default set of quantiles rendered for a histogram when an observer builder isn't
+/// configured with a more specific set via its `quantiles` method: p50, p90, p99, and p999.
+pub fn default_quantiles() -> Vec<Quantile> {
+    vec![
+        Quantile::new(0.5),
+        Quantile::new(0.9),
+        Quantile::new(0.99),
+        Quantile::new(0.999),
+    ]
+}
+
+/// A set of summary statistics computed from a histogram's samples: the basic aggregates plus a
+/// labeled value for each requested [`Quantile`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramSummary {
+    /// The number of samples the summary was computed from.
+    pub count: u64,
+    /// The smallest sample.
+    pub min: u64,
+    /// The largest sample.
+    pub max: u64,
+    /// The sum of all samples.
+    pub sum: u64,
+    /// The value of each requested quantile, paired with the `Quantile` it was computed from, in
+    /// the same order as `quantiles` was given.
+    pub quantiles: Vec<(Quantile, u64)>,
+}
+
+/// Summarizes `values` according to `quantiles`, returning `None` if `values` is empty.
+///
+/// Empty histograms are skipped entirely by callers rather than summarized, since there's nothing
+/// meaningful to compute a rank over.
+///
+/// The quantile value for a given fraction `q` is taken as the sample at
+/// `floor(q * (values.len() - 1))` in the sorted sample set.  We deliberately don't interpolate
+/// between adjacent ranks: these are nanosecond-resolution timings, so the extra precision an
+/// interpolated value would imply isn't meaningful, and picking an exact observed sample keeps the
+/// reported value one that was actually seen.
+pub fn summarize(values: &[u64], quantiles: &[Quantile]) -> Option<HistogramSummary> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+
+    let count = sorted.len() as u64;
+    let min = *sorted.first().unwrap();
+    let max = *sorted.last().unwrap();
+    let sum = sorted.iter().sum();
+
+    let quantiles = quantiles
+        .iter()
+        .map(|q| {
+            let rank = q.value() * ((sorted.len() - 1) as f64);
+            let value = sorted[rank.floor() as usize];
+            (q.clone(), value)
+        })
+        .collect();
+
+    Some(HistogramSummary {
+        count,
+        min,
+        max,
+        sum,
+        quantiles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_formatting() {
+        assert_eq!(Quantile::new(0.5).label(), "5");
+        assert_eq!(Quantile::new(0.9).label(), "9");
+        assert_eq!(Quantile::new(0.99).label(), "99");
+        assert_eq!(Quantile::new(0.999).label(), "999");
+        assert_eq!(Quantile::new(0.0).label(), "0");
+        assert_eq!(Quantile::new(1.0).label(), "1");
+    }
+
+    #[test]
+    fn test_percentile_label_formatting() {
+        assert_eq!(Quantile::new(0.5).percentile(), "50");
+        assert_eq!(Quantile::new(0.9).percentile(), "90");
+        assert_eq!(Quantile::new(0.99).percentile(), "99");
+        assert_eq!(Quantile::new(0.999).percentile(), "999");
+        assert_eq!(Quantile::new(0.0).percentile(), "0");
+        assert_eq!(Quantile::new(1.0).percentile(), "100");
+    }
+
+    #[test]
+    fn test_out_of_range_is_clamped() {
+        assert_eq!(Quantile::new(1.5).value(), 1.0);
+        assert_eq!(Quantile::new(-0.5).value(), 0.0);
+
+        // A clamped quantile must never push `summarize`'s rank calculation out of bounds.
+        let values: Vec<u64> = (1..=10).collect();
+        let summary = summarize(&values, &[Quantile::new(1.5)]).unwrap();
+        assert_eq!(summary.quantiles[0].0.label(), "1");
+        assert_eq!(summary.quantiles[0].1, 10);
+    }
+
+    #[test]
+    fn test_summarize_empty_is_none() {
+        assert!(summarize(&[], &default_quantiles()).is_none());
+    }
+
+    #[test]
+    fn test_summarize_basic() {
+        let values: Vec<u64> = (1..=100).collect();
+        let summary = summarize(&values, &[Quantile::new(0.5), Quantile::new(0.99)]).unwrap();
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.min, 1);
+        assert_eq!(summary.max, 100);
+        assert_eq!(summary.sum, 5050);
+        assert_eq!(summary.quantiles[0].0.label(), "5");
+        assert_eq!(summary.quantiles[0].1, 50);
+        assert_eq!(summary.quantiles[1].0.label(), "99");
+        assert_eq!(summary.quantiles[1].1, 99);
+    }
+
+    #[test]
+    fn test_summarize_single_value() {
+        let summary = summarize(&[42], &default_quantiles()).unwrap();
+        assert_eq!(summary.min, 42);
+        assert_eq!(summary.max, 42);
+        for (_, value) in summary.quantiles {
+            assert_eq!(value, 42);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use metrics_core::{Builder, Drain, Key, Observer};
+
+use crate::observers::core::{default_quantiles, summarize, Quantile};
+
+/// Builds a [`JsonObserver`].
+///
+/// By default, histograms are summarized with [`default_quantiles`]; call
+/// [`JsonBuilder::quantiles`] to render a different set.
+#[derive(Clone, Debug)]
+pub struct JsonBuilder {
+    quantiles: Vec<Quantile>,
+    pretty: bool,
+}
+
+impl JsonBuilder {
+    /// Creates a new `JsonBuilder`.
+    pub fn new() -> JsonBuilder {
+        JsonBuilder {
+            quantiles: default_quantiles(),
+            pretty: false,
+        }
+    }
+
+    /// Sets the quantiles to render for each histogram.
+    pub fn quantiles(mut self, quantiles: &[f64]) -> JsonBuilder {
+        self.quantiles = quantiles.iter().copied().map(Quantile::new).collect();
+        self
+    }
+
+    /// Renders the output with indentation and newlines for readability.
+    pub fn set_pretty_json(mut self, pretty: bool) -> JsonBuilder {
+        self.pretty = pretty;
+        self
+    }
+}
+
+impl Default for JsonBuilder {
+    fn default() -> JsonBuilder {
+        JsonBuilder::new()
+    }
+}
+
+impl Builder for JsonBuilder {
+    type Output = JsonObserver;
+
+    fn build(&self) -> Self::Output {
+        JsonObserver {
+            quantiles: self.quantiles.clone(),
+            pretty: self.pretty,
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+        }
+    }
+}
+
+/// Renders metrics as a JSON document.
+pub struct JsonObserver {
+    quantiles: Vec<Quantile>,
+    pretty: bool,
+    counters: HashMap<String, u64>,
+    gauges: HashMap<String, i64>,
+    histograms: HashMap<String, Vec<u64>>,
+}
+
+impl Observer for JsonObserver {
+    fn observe_counter(&mut self, key: Key, value: u64) {
+        self.counters.insert(key.to_string(), value);
+    }
+
+    fn observe_gauge(&mut self, key: Key, value: i64) {
+        self.gauges.insert(key.to_string(), value);
+    }
+
+    fn observe_histogram(&mut self, key: Key, values: &[u64]) {
+        self.histograms
+            .entry(key.to_string())
+            .or_default()
+            .extend_from_slice(values);
+    }
+}
+
+impl Drain<String> for JsonObserver {
+    fn drain(&mut self) -> String {
+        let mut fields: Vec<(String, String)> = Vec::new();
+
+        for (key, value) in &self.counters {
+            fields.push((key.clone(), value.to_string()));
+        }
+
+        for (key, value) in &self.gauges {
+            fields.push((key.clone(), value.to_string()));
+        }
+
+        for (key, values) in &self.histograms {
+            if let Some(summary) = summarize(values, &self.quantiles) {
+                fields.push((format!("{}.count", key), summary.count.to_string()));
+                fields.push((format!("{}.min", key), summary.min.to_string()));
+                fields.push((format!("{}.max", key), summary.max.to_string()));
+                fields.push((format!("{}.sum", key), summary.sum.to_string()));
+                for (quantile, value) in &summary.quantiles {
+                    fields.push((
+                        format!("{}.p{}", key, quantile.percentile()),
+                        value.to_string(),
+                    ));
+                }
+            }
+        }
+
+        self.counters.clear();
+        self.gauges.clear();
+        self.histograms.clear();
+
+        let (sep, indent) = if self.pretty { (",\n", "  ") } else { (",", "") };
+        let body = fields
+            .iter()
+            .map(|(k, v)| format!("{}\"{}\":{}", indent, k, v))
+            .collect::<Vec<_>>()
+            .join(sep);
+
+        if self.pretty {
+            format!("{{\n{}\n}}", body)
+        } else {
+            format!("{{{}}}", body)
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Pre-baked [`Observer`](metrics_core::Observer)/[`Builder`](metrics_core::Builder)
+//! implementations for rendering a snapshot in a given output format.
+mod core;
+#[cfg(feature = "metrics-observer-json")]
+mod json;
+#[cfg(feature = "metrics-observer-prometheus")]
+mod prometheus;
+#[cfg(feature = "metrics-observer-yaml")]
+mod yaml;
+
+pub use self::core::Quantile;
+#[cfg(feature = "metrics-observer-json")]
+pub use self::json::{JsonBuilder, JsonObserver};
+#[cfg(feature = "metrics-observer-prometheus")]
+pub use self::prometheus::{PrometheusBuilder, PrometheusObserver};
+#[cfg(feature = "metrics-observer-yaml")]
+pub use self::yaml::{YamlBuilder, YamlObserver};
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use metrics_core::{Builder, Drain, Key, Observer};
+
+use crate::observers::core::{default_quantiles, summarize, Quantile};
+
+/// Builds a [`PrometheusObserver`].
+///
+/// By default, histograms are summarized with [`default_quantiles`]; call
+/// [`PrometheusBuilder::quantiles`] to render a different set.
+#[derive(Clone, Debug)]
+pub struct PrometheusBuilder {
+    quantiles: Vec<Quantile>,
+}
+
+impl PrometheusBuilder {
+    /// Creates a new `PrometheusBuilder`.
+    pub fn new() -> PrometheusBuilder {
+        PrometheusBuilder {
+            quantiles: default_quantiles(),
+        }
+    }
+
+    /// Sets the quantiles to render for each histogram.
+    pub fn quantiles(mut self, quantiles: &[f64]) -> PrometheusBuilder {
+        self.quantiles = quantiles.iter().copied().map(Quantile::new).collect();
+        self
+    }
+}
+
+impl Default for PrometheusBuilder {
+    fn default() -> PrometheusBuilder {
+        PrometheusBuilder::new()
+    }
+}
+
+impl Builder for PrometheusBuilder {
+    type Output = PrometheusObserver;
+
+    fn build(&self) -> Self::Output {
+        PrometheusObserver {
+            quantiles: self.quantiles.clone(),
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+        }
+    }
+}
+
+/// Renders metrics in the Prometheus text exposition format.
+pub struct PrometheusObserver {
+    quantiles: Vec<Quantile>,
+    counters: HashMap<String, u64>,
+    gauges: HashMap<String, i64>,
+    histograms: HashMap<String, Vec<u64>>,
+}
+
+impl Observer for PrometheusObserver {
+    fn observe_counter(&mut self, key: Key, value: u64) {
+        self.counters.insert(sanitize(&key.to_string()), value);
+    }
+
+    fn observe_gauge(&mut self, key: Key, value: i64) {
+        self.gauges.insert(sanitize(&key.to_string()), value);
+    }
+
+    fn observe_histogram(&mut self, key: Key, values: &[u64]) {
+        self.histograms
+            .entry(sanitize(&key.to_string()))
+            .or_default()
+            .extend_from_slice(values);
+    }
+}
+
+impl Drain<String> for PrometheusObserver {
+    fn drain(&mut self) -> String {
+        let mut out = String::new();
+
+        for (key, value) in &self.counters {
+            out.push_str(&format!("# TYPE {} counter\n{} {}\n", key, key, value));
+        }
+
+        for (key, value) in &self.gauges {
+            out.push_str(&format!("# TYPE {} gauge\n{} {}\n", key, key, value));
+        }
+
+        for (key, values) in &self.histograms {
+            if let Some(summary) = summarize(values, &self.quantiles) {
+                out.push_str(&format!("# TYPE {} summary\n", key));
+                for (quantile, value) in &summary.quantiles {
+                    out.push_str(&format!(
+                        "{}{{quantile=\"{}\"}} {}\n",
+                        key,
+                        quantile.value(),
+                        value
+                    ));
+                }
+                out.push_str(&format!("{}_sum {}\n", key, summary.sum));
+                out.push_str(&format!("{}_count {}\n", key, summary.count));
+                out.push_str(&format!("{}_min {}\n", key, summary.min));
+                out.push_str(&format!("{}_max {}\n", key, summary.max));
+            }
+        }
+
+        self.counters.clear();
+        self.gauges.clear();
+        self.histograms.clear();
+
+        out
+    }
+}
+
+/// Prometheus metric names may only contain `[a-zA-Z0-9_:]`; anything else -- notably the `.`
+/// scope separator used elsewhere in this crate -- is rewritten to `_`.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect()
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use metrics_core::{Builder, Drain, Key, Observer};
+
+use crate::observers::core::{default_quantiles, summarize, Quantile};
+
+/// Builds a [`YamlObserver`].
+///
+/// By default, histograms are summarized with [`default_quantiles`]; call
+/// [`YamlBuilder::quantiles`] to render a different set.
+#[derive(Clone, Debug)]
+pub struct YamlBuilder {
+    quantiles: Vec<Quantile>,
+}
+
+impl YamlBuilder {
+    /// Creates a new `YamlBuilder`.
+    pub fn new() -> YamlBuilder {
+        YamlBuilder {
+            quantiles: default_quantiles(),
+        }
+    }
+
+    /// Sets the quantiles to render for each histogram.
+    pub fn quantiles(mut self, quantiles: &[f64]) -> YamlBuilder {
+        self.quantiles = quantiles.iter().copied().map(Quantile::new).collect();
+        self
+    }
+}
+
+impl Default for YamlBuilder {
+    fn default() -> YamlBuilder {
+        YamlBuilder::new()
+    }
+}
+
+impl Builder for YamlBuilder {
+    type Output = YamlObserver;
+
+    fn build(&self) -> Self::Output {
+        YamlObserver {
+            quantiles: self.quantiles.clone(),
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+        }
+    }
+}
+
+/// Renders metrics as a simple, YAML-like document.
+pub struct YamlObserver {
+    quantiles: Vec<Quantile>,
+    counters: HashMap<String, u64>,
+    gauges: HashMap<String, i64>,
+    histograms: HashMap<String, Vec<u64>>,
+}
+
+impl Observer for YamlObserver {
+    fn observe_counter(&mut self, key: Key, value: u64) {
+        self.counters.insert(key.to_string(), value);
+    }
+
+    fn observe_gauge(&mut self, key: Key, value: i64) {
+        self.gauges.insert(key.to_string(), value);
+    }
+
+    fn observe_histogram(&mut self, key: Key, values: &[u64]) {
+        self.histograms
+            .entry(key.to_string())
+            .or_default()
+            .extend_from_slice(values);
+    }
+}
+
+impl Drain<String> for YamlObserver {
+    fn drain(&mut self) -> String {
+        let mut out = String::new();
+
+        for (key, value) in &self.counters {
+            out.push_str(&format!("{}: {}\n", key, value));
+        }
+
+        for (key, value) in &self.gauges {
+            out.push_str(&format!("{}: {}\n", key, value));
+        }
+
+        for (key, values) in &self.histograms {
+            if let Some(summary) = summarize(values, &self.quantiles) {
+                out.push_str(&format!("{}.count: {}\n", key, summary.count));
+                out.push_str(&format!("{}.min: {}\n", key, summary.min));
+                out.push_str(&format!("{}.max: {}\n", key, summary.max));
+                out.push_str(&format!("{}.sum: {}\n", key, summary.sum));
+                for (quantile, value) in &summary.quantiles {
+                    out.push_str(&format!("{}.p{}: {}\n", key, quantile.percentile(), value));
+                }
+            }
+        }
+
+        self.counters.clear();
+        self.gauges.clear();
+        self.histograms.clear();
+
+        out
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use crate::builder::Builder;
+use crate::common::Scope;
+use crate::control::Controller;
+use crate::registry::Registry;
+use crate::sink::Sink;
+
+/// A registry of all metrics flowing through the system.
+///
+/// A `Receiver` is the entry point for both sending metrics in, via [`Sink`], and pulling metrics
+/// back out, via [`Controller`].
+pub struct Receiver {
+    registry: Arc<Registry>,
+    #[cfg(feature = "metrics-tracing-context")]
+    with_tracing_context: bool,
+}
+
+impl Receiver {
+    /// Creates a [`Builder`] for configuring a new `Receiver`.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    pub(crate) fn from_parts(
+        registry: Arc<Registry>,
+        #[cfg(feature = "metrics-tracing-context")] with_tracing_context: bool,
+    ) -> Receiver {
+        Receiver {
+            registry,
+            #[cfg(feature = "metrics-tracing-context")]
+            with_tracing_context,
+        }
+    }
+
+    /// Creates a new [`Sink`] bound to the root scope.
+    pub fn sink(&self) -> Sink {
+        Sink::new(
+            Arc::clone(&self.registry),
+            Scope::Root,
+            #[cfg(feature = "metrics-tracing-context")]
+            self.with_tracing_context,
+        )
+    }
+
+    /// Creates a new [`Controller`] for taking snapshots of every metric registered so far.
+    pub fn controller(&self) -> Controller {
+        Controller::new(Arc::clone(&self.registry))
+    }
+
+    /// Installs this `Receiver` as the global `metrics` facade recorder.
+    pub fn install(self) {
+        let recorder = crate::recorder::ReceiverRecorder::from(&self);
+        let _ = metrics::set_boxed_recorder(Box::new(recorder));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Receiver;
+
+    #[test]
+    fn test_sinks_from_same_receiver_share_storage() {
+        let receiver = Receiver::builder().build().unwrap();
+        let first = receiver.sink();
+        let second = receiver.sink();
+
+        first.increment_counter("widgets", 1);
+        second.increment_counter("widgets", 1);
+
+        assert_eq!(first.counter("widgets").value(), 2);
+    }
+
+    #[test]
+    fn test_controller_sees_metrics_from_every_sink() {
+        let receiver = Receiver::builder().build().unwrap();
+        receiver.sink().increment_counter("widgets", 5);
+
+        let snapshot = receiver.controller().snapshot();
+        assert_eq!(snapshot.len(), 1);
+    }
+}
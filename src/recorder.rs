@@ -0,0 +1,87 @@
+//! Bridges this crate's [`Sink`] into the [`metrics`](metrics) facade, so that
+//! [`Receiver::install`](crate::Receiver::install) can be used to back the `counter!`/`gauge!`/
+//! `histogram!` macros.
+use std::cell::RefCell;
+
+use metrics::{Key, Recorder};
+
+use crate::receiver::Receiver;
+use crate::sink::Sink;
+
+thread_local! {
+    static THREAD_SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// A [`Recorder`] that records every metric into a [`Sink`] taken from a [`Receiver`].
+///
+/// Each thread gets its own [`Sink`], lazily cloned from a shared template the first time that
+/// thread records a metric.
+pub struct ReceiverRecorder {
+    template: Sink,
+}
+
+impl From<&Receiver> for ReceiverRecorder {
+    fn from(receiver: &Receiver) -> ReceiverRecorder {
+        ReceiverRecorder {
+            template: receiver.sink(),
+        }
+    }
+}
+
+impl ReceiverRecorder {
+    fn with_sink<F: FnOnce(&mut Sink)>(&self, f: F) {
+        THREAD_SINK.with(|cell| {
+            let mut slot = cell.borrow_mut();
+            let sink = slot.get_or_insert_with(|| self.template.clone());
+            f(sink);
+        });
+    }
+}
+
+impl Recorder for ReceiverRecorder {
+    fn increment_counter(&self, key: Key, value: u64) {
+        self.with_sink(|sink| sink.increment_counter(key.name(), value));
+    }
+
+    fn update_gauge(&self, key: Key, value: i64) {
+        self.with_sink(|sink| sink.update_gauge(key.name(), value));
+    }
+
+    fn record_histogram(&self, key: Key, value: u64) {
+        self.with_sink(|sink| sink.record_value(key.name(), value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics::{Key, Recorder};
+
+    use crate::Receiver;
+
+    use super::ReceiverRecorder;
+
+    #[test]
+    fn test_recorder_dispatches_into_the_receivers_registry() {
+        let receiver = Receiver::builder().build().unwrap();
+        let recorder = ReceiverRecorder::from(&receiver);
+
+        recorder.increment_counter(Key::from_name("widgets"), 5);
+        recorder.update_gauge(Key::from_name("red_balloons"), 99);
+        recorder.record_histogram(Key::from_name("latency_ns"), 42);
+
+        let snapshot = receiver.controller().snapshot();
+        assert_eq!(snapshot.len(), 3);
+    }
+
+    #[test]
+    fn test_repeated_records_update_the_same_metric() {
+        let receiver = Receiver::builder().build().unwrap();
+        let recorder = ReceiverRecorder::from(&receiver);
+
+        recorder.increment_counter(Key::from_name("widgets"), 1);
+        recorder.increment_counter(Key::from_name("widgets"), 1);
+
+        let snapshot = receiver.controller().snapshot();
+        assert_eq!(snapshot.len(), 1);
+    }
+}
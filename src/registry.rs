@@ -0,0 +1,101 @@
+//! The central store of all metrics flowing through a [`Receiver`](crate::Receiver).
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use metrics_core::Key;
+
+use crate::common::Measurement;
+use crate::data::{Counter, Gauge, Histogram};
+
+/// A proxy's closure, invoked at snapshot time to pull in externally-sourced measurements.
+///
+/// See [`Sink::proxy`](crate::Sink::proxy) for how these are registered.
+pub(crate) type ProxyFn = dyn Fn() -> Vec<(Key, Measurement)> + Send + Sync;
+
+/// Holds the live handles for every counter, gauge, and histogram registered so far.
+///
+/// Lookups are keyed by [`Key`], which bundles together a metric's scoped name and labels.
+/// [`Sink`](crate::Sink) uses this to hand out [`Counter`]/[`Gauge`]/[`Histogram`] handles, and
+/// [`Controller`](crate::Controller) uses it to walk every registered metric when taking a
+/// snapshot.
+#[derive(Default)]
+pub struct Registry {
+    counters: RwLock<HashMap<Key, Counter>>,
+    gauges: RwLock<HashMap<Key, Gauge>>,
+    histograms: RwLock<HashMap<Key, Histogram>>,
+    proxies: RwLock<HashMap<Key, Arc<ProxyFn>>>,
+}
+
+impl Registry {
+    /// Creates a new, empty `Registry`.
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// Gets the counter registered under `key`, creating it if it doesn't exist yet.
+    pub fn get_counter(&self, key: Key) -> Counter {
+        if let Some(counter) = self.counters.read().unwrap().get(&key) {
+            return counter.clone();
+        }
+
+        self.counters
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+
+    /// Gets the gauge registered under `key`, creating it if it doesn't exist yet.
+    pub fn get_gauge(&self, key: Key) -> Gauge {
+        if let Some(gauge) = self.gauges.read().unwrap().get(&key) {
+            return gauge.clone();
+        }
+
+        self.gauges
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+
+    /// Gets the histogram registered under `key`, creating it if it doesn't exist yet.
+    pub fn get_histogram(&self, key: Key) -> Histogram {
+        if let Some(histogram) = self.histograms.read().unwrap().get(&key) {
+            return histogram.clone();
+        }
+
+        self.histograms
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+
+    /// Returns a snapshot of every registered counter.
+    pub fn counters(&self) -> HashMap<Key, Counter> {
+        self.counters.read().unwrap().clone()
+    }
+
+    /// Returns a snapshot of every registered gauge.
+    pub fn gauges(&self) -> HashMap<Key, Gauge> {
+        self.gauges.read().unwrap().clone()
+    }
+
+    /// Returns a snapshot of every registered histogram.
+    pub fn histograms(&self) -> HashMap<Key, Histogram> {
+        self.histograms.read().unwrap().clone()
+    }
+
+    /// Registers `proxy` under `key`, replacing any proxy already registered under it.
+    pub fn register_proxy(&self, key: Key, proxy: Arc<ProxyFn>) {
+        self.proxies.write().unwrap().insert(key, proxy);
+    }
+
+    /// Returns every registered proxy, keyed by the name it was registered under.
+    pub fn proxies(&self) -> HashMap<Key, Arc<ProxyFn>> {
+        self.proxies.read().unwrap().clone()
+    }
+}
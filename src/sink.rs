@@ -0,0 +1,294 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+use metrics_core::{Key, Label};
+
+use crate::common::{Delta, Measurement, Scope};
+use crate::data::{Counter, Gauge, Histogram};
+use crate::registry::Registry;
+
+/// A handle for sending metrics into a [`Receiver`](crate::Receiver).
+///
+/// `Sink`s are cheap to clone, and carry their scope and default labels with them: a cloned or
+/// scoped `Sink` starts out with the same default labels as its parent, and can add its own on top.
+#[derive(Clone)]
+pub struct Sink {
+    registry: Arc<Registry>,
+    scope: Scope,
+    default_labels: Vec<(String, String)>,
+    #[cfg(feature = "metrics-tracing-context")]
+    with_tracing_context: bool,
+}
+
+impl Sink {
+    pub(crate) fn new(
+        registry: Arc<Registry>,
+        scope: Scope,
+        #[cfg(feature = "metrics-tracing-context")] with_tracing_context: bool,
+    ) -> Sink {
+        Sink {
+            registry,
+            scope,
+            default_labels: Vec::new(),
+            #[cfg(feature = "metrics-tracing-context")]
+            with_tracing_context,
+        }
+    }
+
+    /// Returns the current time, in nanoseconds since a fixed process-wide starting point,
+    /// suitable for passing to [`Sink::record_timing`].
+    pub fn now(&self) -> u64 {
+        static START: OnceLock<Instant> = OnceLock::new();
+        let start = *START.get_or_init(Instant::now);
+        Instant::now().delta(start)
+    }
+
+    /// Creates a new `Sink` nested under `scope`, relative to this one.
+    pub fn scoped<S: AsScoped>(&self, scope: S) -> Sink {
+        let mut sink = self.clone();
+        sink.scope = scope.as_scoped(self.scope.clone());
+        sink
+    }
+
+    /// Adds labels that will be attached to every metric sent through this `Sink` from now on.
+    ///
+    /// This is additive: calling it multiple times builds up the set of default labels, and
+    /// default labels are inherited when cloning or scoping a `Sink`.
+    pub fn add_default_labels(&mut self, labels: &[(&str, &str)]) {
+        for (key, value) in labels {
+            self.default_labels
+                .push((key.to_string(), value.to_string()));
+        }
+    }
+
+    /// Gets a handle to the counter registered under `name`.
+    pub fn counter<N: Into<String>>(&self, name: N) -> Counter {
+        self.counter_with_labels(name, &[])
+    }
+
+    /// Gets a handle to the counter registered under `name` with `labels`.
+    pub fn counter_with_labels<N: Into<String>>(&self, name: N, labels: &[(&str, &str)]) -> Counter {
+        self.registry.get_counter(self.key_for(name.into(), labels))
+    }
+
+    /// Gets a handle to the gauge registered under `name`.
+    pub fn gauge<N: Into<String>>(&self, name: N) -> Gauge {
+        self.gauge_with_labels(name, &[])
+    }
+
+    /// Gets a handle to the gauge registered under `name` with `labels`.
+    pub fn gauge_with_labels<N: Into<String>>(&self, name: N, labels: &[(&str, &str)]) -> Gauge {
+        self.registry.get_gauge(self.key_for(name.into(), labels))
+    }
+
+    /// Gets a handle to the histogram registered under `name`.
+    pub fn histogram<N: Into<String>>(&self, name: N) -> Histogram {
+        self.histogram_with_labels(name, &[])
+    }
+
+    /// Gets a handle to the histogram registered under `name` with `labels`.
+    pub fn histogram_with_labels<N: Into<String>>(
+        &self,
+        name: N,
+        labels: &[(&str, &str)],
+    ) -> Histogram {
+        self.registry
+            .get_histogram(self.key_for(name.into(), labels))
+    }
+
+    /// Increments the counter registered under `name` by `value`.
+    pub fn increment_counter<N: Into<String>>(&self, name: N, value: u64) {
+        self.counter(name).record(value);
+    }
+
+    /// Sets the gauge registered under `name` to `value`.
+    pub fn update_gauge<N: Into<String>>(&self, name: N, value: i64) {
+        self.gauge(name).record(value);
+    }
+
+    /// Records `value` into the histogram registered under `name`.
+    pub fn record_value<N: Into<String>>(&self, name: N, value: u64) {
+        self.histogram(name).record_value(value);
+    }
+
+    /// Records `value` into the histogram registered under `name`, with `labels`.
+    pub fn record_value_with_labels<N: Into<String>>(
+        &self,
+        name: N,
+        value: u64,
+        labels: &[(&str, &str)],
+    ) {
+        self.histogram_with_labels(name, labels).record_value(value);
+    }
+
+    /// Records the delta between `start` and `end`, in nanoseconds, into the histogram registered
+    /// under `name`.
+    pub fn record_timing<N: Into<String>, D: Delta>(&self, name: N, start: D, end: D) {
+        self.histogram(name).record_timing(start, end);
+    }
+
+    /// Registers a proxy metric under `name`: a closure that's invoked every time a snapshot is
+    /// taken, and whose returned measurements are added to the overall snapshot under `name`.
+    ///
+    /// `name` is scoped exactly like any other metric registered through this `Sink`, and is
+    /// prepended to the name of each measurement the closure returns (e.g. a proxy named
+    /// `"load_stat"` whose closure returns a measurement named `"avg_1min"` contributes
+    /// `"load_stat.avg_1min"` to the snapshot).  Unlike other metrics, proxied measurements don't
+    /// pick up this `Sink`'s default labels or tracing-context labels -- only the scope-derived
+    /// name prefix carries over, matching the measurement's own name and value as returned by the
+    /// closure.
+    pub fn proxy<N, F>(&self, name: N, f: F)
+    where
+        N: Into<String>,
+        F: Fn() -> Vec<(Key, Measurement)> + Send + Sync + 'static,
+    {
+        let key = self.key_for(name.into(), &[]);
+        self.registry.register_proxy(key, Arc::new(f));
+    }
+
+    /// Builds the fully-qualified [`Key`] for a metric named `name` recorded with `labels`.
+    ///
+    /// Labels are layered in order of increasing specificity: this `Sink`'s default labels first,
+    /// then -- if enabled -- the fields of the currently-active `tracing` span, then the labels
+    /// passed in for this specific call.  Where the same label key appears more than once, the
+    /// most specific value wins.
+    fn key_for(&self, name: String, labels: &[(&str, &str)]) -> Key {
+        let scoped_name = match self.scope.clone().into_string() {
+            Some(prefix) => format!("{}.{}", prefix, name),
+            None => name,
+        };
+
+        let mut merged = self.default_labels.clone();
+
+        #[cfg(feature = "metrics-tracing-context")]
+        if self.with_tracing_context {
+            for (key, value) in crate::tracing_context::current_labels() {
+                merged.retain(|(existing_key, _)| existing_key != &key);
+                merged.push((key, value));
+            }
+        }
+
+        for (key, value) in labels {
+            let key = key.to_string();
+            merged.retain(|(existing_key, _)| existing_key != &key);
+            merged.push((key, value.to_string()));
+        }
+
+        let mut key = Key::from_name(scoped_name);
+        key.add_labels(
+            merged
+                .into_iter()
+                .map(|(key, value)| Label::new(key, value))
+                .collect::<Vec<_>>(),
+        );
+        key
+    }
+}
+
+/// A value that can be used to extend a [`Sink`]'s scope.
+pub trait AsScoped {
+    /// Extends `base` with this value, returning the resulting scope.
+    fn as_scoped(&self, base: Scope) -> Scope;
+}
+
+impl AsScoped for &str {
+    fn as_scoped(&self, base: Scope) -> Scope {
+        base.add_part(*self)
+    }
+}
+
+impl AsScoped for &[&str] {
+    fn as_scoped(&self, base: Scope) -> Scope {
+        self.iter().fold(base, |scope, part| scope.add_part(*part))
+    }
+}
+
+/// Errors that can occur while using a [`Sink`].
+#[derive(Debug)]
+pub enum SinkError {
+    /// The requested operation isn't supported for the given metric.
+    Unsupported(String),
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SinkError::Unsupported(reason) => write!(f, "unsupported sink operation: {}", reason),
+        }
+    }
+}
+
+impl Error for SinkError {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::common::Scope;
+    use crate::registry::Registry;
+
+    use super::Sink;
+
+    fn test_sink() -> Sink {
+        Sink::new(
+            Arc::new(Registry::new()),
+            Scope::Root,
+            #[cfg(feature = "metrics-tracing-context")]
+            false,
+        )
+    }
+
+    #[test]
+    fn test_root_sink_has_no_name_prefix() {
+        let sink = test_sink();
+        sink.counter("widgets");
+        let key = sink.registry.counters().into_keys().next().unwrap();
+        assert_eq!(key.name(), "widgets");
+    }
+
+    #[test]
+    fn test_scoped_sink_prefixes_name() {
+        let sink = test_sink().scoped("secret");
+        sink.counter("widgets");
+        let key = sink.registry.counters().into_keys().next().unwrap();
+        assert_eq!(key.name(), "secret.widgets");
+    }
+
+    #[test]
+    fn test_nested_scoping_joins_with_dots() {
+        let sink = test_sink().scoped("a").scoped("b");
+        sink.counter("widgets");
+        let key = sink.registry.counters().into_keys().next().unwrap();
+        assert_eq!(key.name(), "a.b.widgets");
+    }
+
+    #[test]
+    fn test_cloned_sink_shares_underlying_storage() {
+        let sink = test_sink();
+        sink.increment_counter("widgets", 3);
+        let cloned = sink.clone();
+        cloned.increment_counter("widgets", 4);
+
+        assert_eq!(sink.counter("widgets").value(), 7);
+    }
+
+    #[test]
+    fn test_different_default_labels_register_distinct_metrics() {
+        let mut a = test_sink();
+        a.add_default_labels(&[("env", "prod")]);
+        let mut b = test_sink();
+        b.add_default_labels(&[("env", "dev")]);
+
+        // Both sinks share the same underlying registry via cloning from a common root in real
+        // usage; here we instead point them at the same one directly to check that differing
+        // default labels produce distinct registry entries for the same metric name.
+        let registry = Arc::clone(&a.registry);
+        b.registry = registry;
+
+        a.counter("widgets");
+        b.counter("widgets");
+        assert_eq!(a.registry.counters().len(), 2);
+    }
+}
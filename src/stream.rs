@@ -0,0 +1,212 @@
+//! Compact, lossless encoding for streams of integer samples.
+//!
+//! Histograms can end up holding millions of raw `u64` samples, and for high-cardinality timing
+//! histograms in particular, the memory overhead of storing each sample as a full 8-byte word adds
+//! up quickly.  [`StreamingIntegers`] stores the same logical sequence far more cheaply by encoding
+//! the delta between consecutive values instead of the values themselves: most real-world samples
+//! (timings, counts) drift by a small amount from one to the next, so the deltas are small and
+//! compress well with a variable-length encoding.
+//!
+//! The scheme used is:
+//!  - the first value is stored raw, as an unsigned LEB128 varint
+//!  - every subsequent value is stored as the signed delta from the previous value, zigzag-encoded
+//!    to map small negative numbers to small positive numbers, and then written as an unsigned
+//!    LEB128 varint
+//!
+//! This is a well-known technique (the same one used by, e.g., Lucene's posting lists and many
+//! time-series databases) and is cheap enough to decode that we can afford to keep histogram
+//! storage compressed at rest and only pay the decompression cost when a snapshot is taken.
+
+/// A compressed, append-only stream of `u64` integers.
+///
+/// Values are compressed as they're pushed in, so the in-memory representation never holds more
+/// than the compressed bytes.  Use [`StreamingIntegers::decompress`] to get an iterator that
+/// reconstructs the original sequence.
+#[derive(Clone, Debug, Default)]
+pub struct StreamingIntegers {
+    data: Vec<u8>,
+    last: Option<u64>,
+}
+
+impl StreamingIntegers {
+    /// Creates a new, empty `StreamingIntegers`.
+    pub fn new() -> StreamingIntegers {
+        StreamingIntegers {
+            data: Vec::new(),
+            last: None,
+        }
+    }
+
+    /// Compresses and appends the given values to this stream.
+    ///
+    /// Values are encoded in order, each as the delta from the previously pushed value -- whether
+    /// that value was pushed in this call or a prior one.
+    pub fn compress(&mut self, values: &[u64]) {
+        for &value in values {
+            match self.last {
+                None => encode_varint(value, &mut self.data),
+                Some(last) => {
+                    let delta = (value as i64).wrapping_sub(last as i64);
+                    encode_varint(zigzag_encode(delta), &mut self.data);
+                }
+            }
+            self.last = Some(value);
+        }
+    }
+
+    /// Returns an iterator that decompresses this stream back into the original sequence of
+    /// values, in the order they were pushed.
+    pub fn decompress(&self) -> Decompressed<'_> {
+        Decompressed {
+            data: &self.data,
+            pos: 0,
+            last: None,
+        }
+    }
+
+    /// Returns the number of bytes used to store the compressed stream.
+    pub fn compressed_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if no values have been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// An iterator that reconstructs the original values from a [`StreamingIntegers`] stream.
+pub struct Decompressed<'a> {
+    data: &'a [u8],
+    pos: usize,
+    last: Option<u64>,
+}
+
+impl<'a> Iterator for Decompressed<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+
+        let (raw, consumed) = decode_varint(&self.data[self.pos..]);
+        self.pos += consumed;
+
+        let value = match self.last {
+            None => raw,
+            Some(last) => {
+                let delta = zigzag_decode(raw);
+                (last as i64).wrapping_add(delta) as u64
+            }
+        };
+        self.last = Some(value);
+        Some(value)
+    }
+}
+
+/// Maps a signed integer to an unsigned integer such that small absolute values -- positive or
+/// negative -- map to small unsigned values, which keeps the variable-length encoding compact.
+fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+/// Reverses [`zigzag_encode`].
+fn zigzag_decode(z: u64) -> i64 {
+    ((z >> 1) as i64) ^ -((z & 1) as i64)
+}
+
+/// Encodes `value` as an unsigned LEB128 varint, appending the bytes to `out`.
+fn encode_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Decodes an unsigned LEB128 varint from the front of `data`, returning the decoded value along
+/// with the number of bytes consumed.
+fn decode_varint(data: &[u8]) -> (u64, usize) {
+    let mut value = 0u64;
+    let mut shift = 0;
+    let mut consumed = 0;
+
+    for &byte in data {
+        consumed += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+
+    (value, consumed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StreamingIntegers;
+
+    fn roundtrip(values: &[u64]) {
+        let mut stream = StreamingIntegers::new();
+        stream.compress(values);
+        let decoded: Vec<u64> = stream.decompress().collect();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn test_empty() {
+        roundtrip(&[]);
+    }
+
+    #[test]
+    fn test_single_value() {
+        roundtrip(&[42]);
+    }
+
+    #[test]
+    fn test_small_deltas() {
+        roundtrip(&[100, 101, 99, 100, 100, 105, 95]);
+    }
+
+    #[test]
+    fn test_monotonic_timings() {
+        let values: Vec<u64> = (0..1000).map(|i| i * 1000).collect();
+        roundtrip(&values);
+    }
+
+    #[test]
+    fn test_full_range_deltas() {
+        roundtrip(&[0, u64::MAX, 0, u64::MAX, i64::MAX as u64]);
+    }
+
+    #[test]
+    fn test_incremental_compress_matches_batch() {
+        let mut incremental = StreamingIntegers::new();
+        incremental.compress(&[1, 2, 3]);
+        incremental.compress(&[4, 5, 6]);
+
+        let mut batch = StreamingIntegers::new();
+        batch.compress(&[1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            incremental.decompress().collect::<Vec<_>>(),
+            batch.decompress().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_compressed_len_smaller_than_raw() {
+        let values: Vec<u64> = (0..1000).map(|i| 1_000_000 + i).collect();
+        let mut stream = StreamingIntegers::new();
+        stream.compress(&values);
+        assert!(stream.compressed_len() < values.len() * 8);
+    }
+}
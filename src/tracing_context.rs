@@ -0,0 +1,180 @@
+//! Opt-in integration that harvests fields from the currently-active `tracing` span and surfaces
+//! them as metric labels, so a request-scoped `span!(Level::INFO, "request", path = "/checkout")`
+//! automatically tags every metric recorded underneath it -- without threading a label slice
+//! through every call site.
+//!
+//! Install [`MetricsLayer`] on your `tracing` subscriber, and build your [`Receiver`](crate::Receiver)
+//! with [`Builder::with_tracing_context`](crate::Builder::with_tracing_context), and every
+//! [`Sink`](crate::Sink) created from it will merge the active span's fields into its metrics'
+//! labels, after the `Sink`'s own default labels and before any labels passed in for a specific
+//! call.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use tracing::field::{Field, Visit};
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+thread_local! {
+    /// The span IDs currently active on this thread, outermost (root) first.
+    static ACTIVE_SPANS: RefCell<Vec<span::Id>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A span's recorded fields, as stringified key/value pairs.
+type SpanFields = Vec<(String, String)>;
+
+/// The recorded fields of every live span, keyed by span ID.
+static SPAN_FIELDS: Lazy<RwLock<HashMap<span::Id, SpanFields>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// A `tracing_subscriber` [`Layer`] that records each span's fields and tracks which spans are
+/// currently active on each thread, so [`current_labels`] can look them up when a metric is
+/// recorded.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsLayer;
+
+impl MetricsLayer {
+    /// Creates a new `MetricsLayer`.
+    pub fn new() -> MetricsLayer {
+        MetricsLayer
+    }
+}
+
+impl<S: Subscriber> Layer<S> for MetricsLayer {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, _ctx: Context<'_, S>) {
+        let mut collector = FieldCollector::default();
+        attrs.record(&mut collector);
+        SPAN_FIELDS.write().unwrap().insert(id.clone(), collector.0);
+    }
+
+    fn on_enter(&self, id: &span::Id, _ctx: Context<'_, S>) {
+        ACTIVE_SPANS.with(|stack| stack.borrow_mut().push(id.clone()));
+    }
+
+    fn on_exit(&self, id: &span::Id, _ctx: Context<'_, S>) {
+        ACTIVE_SPANS.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().rposition(|active| active == id) {
+                stack.remove(pos);
+            }
+        });
+    }
+
+    fn on_close(&self, id: span::Id, _ctx: Context<'_, S>) {
+        SPAN_FIELDS.write().unwrap().remove(&id);
+    }
+}
+
+/// Collects a span's fields as stringified key/value pairs.
+#[derive(Default)]
+struct FieldCollector(SpanFields);
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+/// Returns the fields of every span currently active on this thread, concatenated from the root
+/// span down to the innermost one, with duplicate keys resolved in favor of the innermost span.
+pub fn current_labels() -> Vec<(String, String)> {
+    ACTIVE_SPANS.with(|stack| {
+        let stack = stack.borrow();
+        let fields = SPAN_FIELDS.read().unwrap();
+
+        let mut merged: Vec<(String, String)> = Vec::new();
+        for id in stack.iter() {
+            if let Some(span_fields) = fields.get(id) {
+                for (key, value) in span_fields {
+                    merged.retain(|(existing_key, _)| existing_key != key);
+                    merged.push((key.clone(), value.clone()));
+                }
+            }
+        }
+        merged
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::{span, Level};
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::Registry as SubscriberRegistry;
+
+    use super::{current_labels, MetricsLayer};
+
+    #[test]
+    fn test_no_active_span_yields_no_labels() {
+        assert!(current_labels().is_empty());
+    }
+
+    #[test]
+    fn test_single_span_fields_are_captured() {
+        let subscriber = SubscriberRegistry::default().with(MetricsLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = span!(Level::INFO, "request", path = "/checkout");
+            let _guard = span.enter();
+            assert_eq!(
+                current_labels(),
+                vec![("path".to_string(), "/checkout".to_string())]
+            );
+        });
+    }
+
+    #[test]
+    fn test_nested_spans_concatenate_root_to_leaf() {
+        let subscriber = SubscriberRegistry::default().with(MetricsLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = span!(Level::INFO, "request", path = "/checkout");
+            let _outer_guard = outer.enter();
+            let inner = span!(Level::INFO, "db_query", table = "orders");
+            let _inner_guard = inner.enter();
+
+            let mut labels = current_labels();
+            labels.sort();
+            let mut expected = vec![
+                ("path".to_string(), "/checkout".to_string()),
+                ("table".to_string(), "orders".to_string()),
+            ];
+            expected.sort();
+            assert_eq!(labels, expected);
+        });
+    }
+
+    #[test]
+    fn test_innermost_span_wins_on_duplicate_keys() {
+        let subscriber = SubscriberRegistry::default().with(MetricsLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = span!(Level::INFO, "request", path = "/checkout");
+            let _outer_guard = outer.enter();
+            let inner = span!(Level::INFO, "retry", path = "/checkout/retry");
+            let _inner_guard = inner.enter();
+
+            assert_eq!(
+                current_labels(),
+                vec![("path".to_string(), "/checkout/retry".to_string())]
+            );
+        });
+    }
+
+    #[test]
+    fn test_labels_clear_after_span_exits() {
+        let subscriber = SubscriberRegistry::default().with(MetricsLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            {
+                let span = span!(Level::INFO, "request", path = "/checkout");
+                let _guard = span.enter();
+                assert!(!current_labels().is_empty());
+            }
+            assert!(current_labels().is_empty());
+        });
+    }
+}